@@ -0,0 +1,186 @@
+//! On-disk semantic search index.
+//!
+//! There's no bundled ML model, so "embedding" here means a cheap, fully
+//! deterministic feature-hashed bag-of-words vector: good enough to rank
+//! entries by shared vocabulary/word-stems without needing network access
+//! or a multi-hundred-megabyte model file. Vectors are cached to disk keyed
+//! by a content hash of the sheets they were built from, so re-embedding
+//! only happens when a cheatsheet actually changes.
+
+use crate::CheatSheet;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Fixed dimensionality every cached vector (and every query vector) must
+/// share so cosine similarity is always comparable.
+const EMBEDDING_DIM: usize = 128;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingEntry {
+    pub sheet_id: String,
+    pub section_index: usize,
+    pub item_index: usize,
+    pub vector: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct EmbeddingCache {
+    content_hash: u64,
+    entries: Vec<EmbeddingEntry>,
+}
+
+fn cache_path(config_dir: &Path) -> PathBuf {
+    config_dir.join("embeddings.json")
+}
+
+/// Hash the sheets' content so the cache can tell when it's stale.
+fn content_hash(sheets: &[CheatSheet]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for sheet in sheets {
+        sheet.id.hash(&mut hasher);
+        if let Ok(json) = serde_json::to_string(&sheet.sections) {
+            json.hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Feature-hash `text` into a unit-length vector of [`EMBEDDING_DIM`]
+/// dimensions: each lowercased word is hashed into a bucket and contributes
+/// +1/-1 depending on a second hash bit, which is the standard "hashing
+/// trick" used to avoid keeping a vocabulary around.
+pub fn embed_text(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0f32; EMBEDDING_DIM];
+
+    for word in text.split_whitespace() {
+        let word = word.to_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        word.hash(&mut hasher);
+        let h = hasher.finish();
+
+        let bucket = (h % EMBEDDING_DIM as u64) as usize;
+        let sign = if (h >> 32) % 2 == 0 { 1.0 } else { -1.0 };
+        vector[bucket] += sign;
+    }
+
+    normalize(&mut vector);
+    vector
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for v in vector.iter_mut() {
+            *v /= norm;
+        }
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+/// Rebuild the on-disk embedding index for `sheets`, skipping the work
+/// entirely if the cached content hash already matches.
+pub fn build_index(config_dir: &Path, sheets: &[CheatSheet]) -> Result<(), String> {
+    let hash = content_hash(sheets);
+    let path = cache_path(config_dir);
+
+    if let Ok(existing) = load_cache(&path) {
+        if existing.content_hash == hash {
+            return Ok(());
+        }
+    }
+
+    let mut entries = Vec::new();
+    for sheet in sheets {
+        for (section_index, section) in sheet.sections.iter().enumerate() {
+            let Some(items) = section.get("items").and_then(|v| v.as_array()) else {
+                continue;
+            };
+            for (item_index, item) in items.iter().enumerate() {
+                let text = item_text(item);
+                entries.push(EmbeddingEntry {
+                    sheet_id: sheet.id.clone(),
+                    section_index,
+                    item_index,
+                    vector: embed_text(&text),
+                });
+            }
+        }
+    }
+
+    let cache = EmbeddingCache {
+        content_hash: hash,
+        entries,
+    };
+    let content = serde_json::to_string(&cache).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+/// Concatenate `desc`, `tags`, and `hint` the same way the item is embedded,
+/// since `CheatSheet.sections` is kept as raw JSON on the backend.
+fn item_text(item: &serde_json::Value) -> String {
+    let mut parts = Vec::new();
+    if let Some(desc) = item.get("desc").and_then(|v| v.as_str()) {
+        parts.push(desc.to_string());
+    }
+    if let Some(tags) = item.get("tags").and_then(|v| v.as_array()) {
+        for tag in tags {
+            if let Some(tag) = tag.as_str() {
+                parts.push(tag.to_string());
+            }
+        }
+    }
+    if let Some(hint) = item.get("hint").and_then(|v| v.as_str()) {
+        parts.push(hint.to_string());
+    }
+    parts.join(" ")
+}
+
+fn load_cache(path: &Path) -> Result<EmbeddingCache, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// Rank cached entries by cosine similarity to `query`, returning the top
+/// `top_k` as `(sheet_id, section_index, item_index, similarity)`. When
+/// `global` is `false` the ranking is restricted to `sheet_id`; when `true`
+/// every sheet's entries are ranked together so results can span sheets.
+pub fn search(
+    config_dir: &Path,
+    sheet_id: &str,
+    query: &str,
+    top_k: usize,
+    global: bool,
+) -> Result<Vec<(String, usize, usize, f32)>, String> {
+    let cache = load_cache(&cache_path(config_dir))?;
+    let query_vector = embed_text(query);
+
+    let mut scored: Vec<(String, usize, usize, f32)> = cache
+        .entries
+        .iter()
+        .filter(|entry| global || entry.sheet_id == sheet_id)
+        .map(|entry| {
+            (
+                entry.sheet_id.clone(),
+                entry.section_index,
+                entry.item_index,
+                cosine_similarity(&query_vector, &entry.vector),
+            )
+        })
+        .filter(|(_, _, _, score)| *score > 0.0)
+        .collect();
+
+    scored.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal));
+    scored.truncate(top_k);
+
+    Ok(scored)
+}
@@ -0,0 +1,69 @@
+//! Syntax highlighting for key chips and command hints, backed by a
+//! syntect-style highlighter so a single line of shell/vim/regex can be
+//! broken into styled spans the frontend renders as colored `<span>`s.
+
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{FontStyle, ThemeSet};
+use syntect::parsing::SyntaxSet;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StyledSpan {
+    pub text: String,
+    pub color: String,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+
+/// Highlight `text` using the grammar named by `lang` (e.g. `"bash"`,
+/// `"vim"`, `"regex"`). Falls back to a single unstyled span when the
+/// grammar isn't recognized.
+pub fn highlight(text: &str, lang: &str) -> Vec<StyledSpan> {
+    let syntax_set = SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines);
+    let theme_set = THEME_SET.get_or_init(ThemeSet::load_defaults);
+
+    let Some(syntax) = syntax_set.find_syntax_by_token(lang) else {
+        return vec![plain(text)];
+    };
+    let theme = &theme_set.themes["base16-ocean.dark"];
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let mut spans = Vec::new();
+    for line in text.lines() {
+        let Ok(ranges) = highlighter.highlight_line(line, syntax_set) else {
+            spans.push(plain(line));
+            continue;
+        };
+        for (style, piece) in ranges {
+            spans.push(StyledSpan {
+                text: piece.to_string(),
+                color: format!(
+                    "#{:02x}{:02x}{:02x}",
+                    style.foreground.r, style.foreground.g, style.foreground.b
+                ),
+                bold: style.font_style.contains(FontStyle::BOLD),
+                italic: style.font_style.contains(FontStyle::ITALIC),
+            });
+        }
+    }
+
+    if spans.is_empty() {
+        vec![plain(text)]
+    } else {
+        spans
+    }
+}
+
+fn plain(text: &str) -> StyledSpan {
+    StyledSpan {
+        text: text.to_string(),
+        color: "inherit".to_string(),
+        bold: false,
+        italic: false,
+    }
+}
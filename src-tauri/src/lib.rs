@@ -3,12 +3,17 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::Mutex;
 
+mod embedding;
+mod highlight;
+mod logging;
+mod window_state;
+
 // Global state to store the captured process before showing window
 static CAPTURED_PROCESS: Mutex<Option<String>> = Mutex::new(None);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct CheatSheet {
-    id: String,
+pub(crate) struct CheatSheet {
+    pub(crate) id: String,
     name: String,
     #[serde(default)]
     description: String,
@@ -16,7 +21,9 @@ struct CheatSheet {
     hint: Option<String>,
     #[serde(default)]
     processes: Vec<String>,
-    sections: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub(crate) synonyms: Vec<Vec<String>>,
+    pub(crate) sections: Vec<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -27,12 +34,46 @@ struct AppConfig {
     last_cheatsheet_per_process: std::collections::HashMap<String, String>,
     #[serde(default = "default_search_all_for_process")]
     search_all_for_process: bool,
+    #[serde(default = "default_rank_criteria")]
+    rank_criteria: Vec<RankCriterion>,
+    #[serde(default = "default_visible_on_all_workspaces")]
+    visible_on_all_workspaces: bool,
+    #[serde(default = "default_global_shortcut")]
+    global_shortcut: String,
 }
 
 fn default_search_all_for_process() -> bool {
     true
 }
 
+fn default_visible_on_all_workspaces() -> bool {
+    true
+}
+
+fn default_global_shortcut() -> String {
+    "super+slash".to_string()
+}
+
+/// Mirrors the frontend's `RankCriterion`; the backend only persists this
+/// list, it never compares matches itself.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RankCriterion {
+    Score,
+    Length,
+    Begin,
+    Index,
+}
+
+fn default_rank_criteria() -> Vec<RankCriterion> {
+    vec![
+        RankCriterion::Score,
+        RankCriterion::Begin,
+        RankCriterion::Length,
+        RankCriterion::Index,
+    ]
+}
+
 fn get_config_path() -> Option<PathBuf> {
     #[cfg(target_os = "windows")]
     let config_dir = std::env::var("APPDATA")
@@ -121,7 +162,113 @@ fn get_active_process_name() -> Option<String> {
     }
 }
 
-#[cfg(not(target_os = "windows"))]
+// Get the active window's process name (macOS-specific)
+#[cfg(target_os = "macos")]
+fn get_active_process_name() -> Option<String> {
+    use cocoa::base::{id, nil};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let frontmost_app: id = msg_send![workspace, frontmostApplication];
+        if frontmost_app == nil {
+            return None;
+        }
+
+        let name: id = msg_send![frontmost_app, localizedName];
+        if name == nil {
+            return None;
+        }
+
+        let utf8: *const std::os::raw::c_char = msg_send![name, UTF8String];
+        if utf8.is_null() {
+            return None;
+        }
+
+        Some(
+            std::ffi::CStr::from_ptr(utf8)
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+}
+
+// Get the active window's process name (X11-specific; gracefully falls
+// back to `None` under a Wayland session where this query isn't possible).
+#[cfg(target_os = "linux")]
+fn get_active_process_name() -> Option<String> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+
+    let (conn, screen_num) = x11rb::connect(None).ok()?;
+    let root = conn.setup().roots.get(screen_num)?.root;
+
+    let net_active_window = conn
+        .intern_atom(false, b"_NET_ACTIVE_WINDOW")
+        .ok()?
+        .reply()
+        .ok()?
+        .atom;
+    let net_wm_pid = conn
+        .intern_atom(false, b"_NET_WM_PID")
+        .ok()?
+        .reply()
+        .ok()?
+        .atom;
+
+    let active_window = conn
+        .get_property(false, root, net_active_window, AtomEnum::WINDOW, 0, 1)
+        .ok()?
+        .reply()
+        .ok()?
+        .value32()?
+        .next()?;
+    if active_window == 0 {
+        return None;
+    }
+
+    let pid = conn
+        .get_property(false, active_window, net_wm_pid, AtomEnum::CARDINAL, 0, 1)
+        .ok()?
+        .reply()
+        .ok()?
+        .value32()?
+        .next()?;
+    if pid == 0 {
+        return None;
+    }
+
+    process_name_from_pid(pid)
+}
+
+/// Resolve a process name from its pid via procfs. `/proc/<pid>/comm` is
+/// truncated by the kernel to 15 bytes (16 with the trailing NUL), so fall
+/// back to the `exe` symlink's file name when `comm` is 15 bytes or longer
+/// (and so may have been cut off) or is unreadable.
+#[cfg(target_os = "linux")]
+fn process_name_from_pid(pid: u32) -> Option<String> {
+    let comm = fs::read_to_string(format!("/proc/{}/comm", pid))
+        .ok()
+        .map(|comm| comm.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    if let Some(name) = &comm {
+        if name.len() < 15 {
+            return Some(name.clone());
+        }
+    }
+
+    fs::read_link(format!("/proc/{}/exe", pid))
+        .ok()
+        .and_then(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|s| s.to_string())
+        })
+        .or(comm)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
 fn get_active_process_name() -> Option<String> {
     None
 }
@@ -169,6 +316,35 @@ fn toggle_search_all_for_process() -> Result<bool, String> {
     Ok(config.search_all_for_process)
 }
 
+/// Toggle verbose logging (forces the log level to at least `Debug`
+/// regardless of `RUST_LOG`), so a user can turn up detail for a bug report
+/// without restarting the app with a different environment variable.
+#[tauri::command]
+fn toggle_verbose_logging() -> bool {
+    logging::set_verbose(!logging::is_verbose())
+}
+
+#[tauri::command]
+fn set_rank_criteria(criteria: Vec<RankCriterion>) -> Result<(), String> {
+    let mut config = load_config();
+    config.rank_criteria = criteria;
+    save_config(&config)
+}
+
+#[tauri::command]
+fn set_visible_on_all_workspaces<R: Runtime>(
+    window: tauri::WebviewWindow<R>,
+    enabled: bool,
+) -> Result<(), String> {
+    window
+        .set_visible_on_all_workspaces(enabled)
+        .map_err(|e| e.to_string())?;
+
+    let mut config = load_config();
+    config.visible_on_all_workspaces = enabled;
+    save_config(&config)
+}
+
 #[tauri::command]
 fn get_initial_sheet_id() -> Option<String> {
     let config = load_config();
@@ -217,8 +393,48 @@ fn set_window_size_from_screen<R: Runtime>(window: tauri::WebviewWindow<R>) -> R
 }
 
 #[tauri::command]
-fn close_window(window: tauri::Window) {
-    window.hide().unwrap();
+fn close_window<R: Runtime>(window: tauri::WebviewWindow<R>) {
+    hide_and_capture(&window);
+}
+
+#[tauri::command]
+fn copy_to_clipboard<R: Runtime>(app: tauri::AppHandle<R>, text: String) -> Result<(), String> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+    app.clipboard().write_text(text).map_err(|e| e.to_string())
+}
+
+fn config_dir() -> Result<PathBuf, String> {
+    get_config_path()
+        .and_then(|p| p.parent().map(|d| d.to_path_buf()))
+        .ok_or_else(|| "Failed to get config directory".to_string())
+}
+
+#[tauri::command]
+fn build_embedding_index(sheets: Vec<CheatSheet>) -> Result<(), String> {
+    embedding::build_index(&config_dir()?, &sheets)
+}
+
+#[tauri::command]
+fn semantic_search(
+    sheet_id: String,
+    query: String,
+    top_k: usize,
+    global: bool,
+) -> Result<Vec<(String, usize, usize, f32)>, String> {
+    embedding::search(&config_dir()?, &sheet_id, &query, top_k, global)
+}
+
+#[tauri::command]
+fn highlight_text(text: String, lang: Option<String>) -> Vec<highlight::StyledSpan> {
+    match lang {
+        Some(lang) => highlight::highlight(&text, &lang),
+        None => vec![highlight::StyledSpan {
+            text,
+            color: "inherit".to_string(),
+            bold: false,
+            italic: false,
+        }],
+    }
 }
 
 #[tauri::command]
@@ -272,7 +488,7 @@ fn load_cheatsheets(app: tauri::AppHandle) -> Result<Vec<CheatSheet>, String> {
     });
 
     if let Some(dev_dir) = project_dir {
-        eprintln!(
+        log::debug!(
             "Checking dev/project directory: {:?} (exists: {})",
             dev_dir,
             dev_dir.exists()
@@ -285,7 +501,7 @@ fn load_cheatsheets(app: tauri::AppHandle) -> Result<Vec<CheatSheet>, String> {
     // 2. Production: Add bundled cheatsheets directory (in resources)
     if let Ok(resource_dir) = app.path().resource_dir() {
         let bundled_dir = resource_dir.join("cheatsheets");
-        eprintln!(
+        log::debug!(
             "Checking bundled directory: {:?} (exists: {})",
             bundled_dir,
             bundled_dir.exists()
@@ -320,47 +536,47 @@ fn load_cheatsheets(app: tauri::AppHandle) -> Result<Vec<CheatSheet>, String> {
     });
 
     if let Some(user_dir) = user_dir {
-        eprintln!("Checking user data directory: {:?}", user_dir);
+        log::debug!("Checking user data directory: {:?}", user_dir);
         // Create the directory if it doesn't exist
         let _ = fs::create_dir_all(&user_dir);
         if user_dir.exists() {
-            eprintln!("User data directory exists, adding to search list");
+            log::debug!("User data directory exists, adding to search list");
             dirs_to_search.push(user_dir);
         }
     }
 
-    eprintln!(
+    log::info!(
         "Searching {} directories for JSON files",
         dirs_to_search.len()
     );
 
     // Search all directories for JSON files
     for dir in dirs_to_search {
-        eprintln!("Searching directory: {:?}", dir);
+        log::debug!("Searching directory: {:?}", dir);
         if let Ok(entries) = fs::read_dir(&dir) {
             for entry in entries.flatten() {
                 let path = entry.path();
-                eprintln!("Found file: {:?}", path);
+                log::debug!("Found file: {:?}", path);
                 if path.extension().and_then(|s| s.to_str()) == Some("json") {
-                    eprintln!("Loading JSON file: {:?}", path);
+                    log::debug!("Loading JSON file: {:?}", path);
                     match load_cheatsheet_from_file(&path) {
                         Ok(sheet) => {
-                            eprintln!(
+                            log::info!(
                                 "Successfully loaded sheet: {} (id: {})",
                                 sheet.name, sheet.id
                             );
                             sheets.push(sheet);
                         }
-                        Err(e) => eprintln!("Failed to load {:?}: {}", path, e),
+                        Err(e) => log::warn!("Failed to load {:?}: {}", path, e),
                     }
                 }
             }
         } else {
-            eprintln!("Failed to read directory: {:?}", dir);
+            log::warn!("Failed to read directory: {:?}", dir);
         }
     }
 
-    eprintln!("Total sheets loaded: {}", sheets.len());
+    log::info!("Total sheets loaded: {}", sheets.len());
     Ok(sheets)
 }
 
@@ -375,14 +591,19 @@ fn load_cheatsheet_from_file(path: &PathBuf) -> Result<CheatSheet, String> {
 
 use tauri::{
     AppHandle, Emitter, Listener, Manager, Runtime,
-    menu::{MenuBuilder, MenuItemBuilder},
+    menu::{CheckMenuItemBuilder, Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder},
     tray::TrayIconBuilder,
 };
 
+const TRAY_ID: &str = "main-tray";
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    logging::init(config_dir().ok().as_deref());
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .setup(|app| {
             setup_tray(app)?;
             #[cfg(desktop)]
@@ -390,63 +611,169 @@ pub fn run() {
             #[cfg(target_os = "windows")]
             configure_windows_styles(app)?;
             setup_blur_handler(app)?;
+            apply_visible_on_all_workspaces(app);
             Ok(())
         })
         // Commands
         .invoke_handler(tauri::generate_handler![
             close_window,
+            copy_to_clipboard,
             load_cheatsheets,
             get_current_process,
             get_config,
             update_last_cheatsheet,
             toggle_search_all_for_process,
+            toggle_verbose_logging,
+            set_rank_criteria,
+            set_visible_on_all_workspaces,
+            set_global_shortcut,
             get_initial_sheet_id,
             get_sheet_for_process,
-            set_window_size_from_screen
+            set_window_size_from_screen,
+            build_embedding_index,
+            semantic_search,
+            highlight_text
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
+/// Persist `sheet_id` as `last_cheatsheet_id` so a tray-menu pick is
+/// reflected by `get_initial_sheet_id` (and thus the submenu's checkmark on
+/// `rebuild_tray_menu`) immediately, instead of waiting on the frontend's
+/// asynchronous `update_last_cheatsheet` invoke triggered off the
+/// `switch-cheatsheet` event.
+fn persist_last_cheatsheet_id(sheet_id: &str) {
+    let mut config = load_config();
+    config.last_cheatsheet_id = Some(sheet_id.to_string());
+    let _ = save_config(&config);
+}
+
 /// Build tray icon and menu: Toggle, Reload, Open Cheatsheets, Quit.
-fn setup_tray<R: Runtime>(app: &mut tauri::App<R>) -> tauri::Result<()> {
-    // Tray menu items
-    let toggle_item = MenuItemBuilder::new("Toggle").id("toggle").build(app)?;
+fn setup_tray(app: &mut tauri::App) -> tauri::Result<()> {
+    let handle = app.handle().clone();
+    let tray_menu = build_tray_menu(&handle)?;
+
+    // Load tray icon from src-tauri/icons/tray.png; fall back to default app icon if missing.
+    let icon = load_tray_icon(app);
+
+    let mut tray_builder = TrayIconBuilder::with_id(TRAY_ID).tooltip("Cheatrs");
+    if let Some(icon) = icon {
+        tray_builder = tray_builder.icon(icon);
+    }
+
+    tray_builder
+        .menu(&tray_menu)
+        .on_menu_event(|app_handle, event| {
+            let id = event.id().as_ref();
+            if let Some(sheet_id) = id.strip_prefix("sheet:") {
+                persist_last_cheatsheet_id(sheet_id);
+                let _ = app_handle.emit("switch-cheatsheet", sheet_id);
+                rebuild_tray_menu(app_handle);
+                return;
+            }
+            match id {
+                "toggle" => toggle_main_window_visibility(app_handle),
+                "reload" => {
+                    reload_main_window(app_handle);
+                    rebuild_tray_menu(app_handle);
+                }
+                "open_cheatsheets" => open_cheatsheets_folder(app_handle),
+                "toggle_verbose_logging" => {
+                    toggle_verbose_logging();
+                    rebuild_tray_menu(app_handle);
+                }
+                "quit" => {
+                    if let Some(window) = app_handle.get_webview_window("main") {
+                        capture_window_state(&window);
+                    }
+                    app_handle.exit(0)
+                }
+                _ => {}
+            }
+        })
+        .build(app)?;
+
+    Ok(())
+}
+
+/// Build the tray menu: the static Toggle/Reload/Open/Verbose/Quit items
+/// plus a "Cheatsheets" submenu listing whatever `load_cheatsheets`
+/// currently finds on disk, with the active sheet checked. The Toggle
+/// item's label shows the configured hotkey so users can see their binding
+/// from the tray.
+fn build_tray_menu(app: &AppHandle) -> tauri::Result<Menu> {
+    let hotkey = load_config().global_shortcut;
+    let toggle_item = MenuItemBuilder::new(format!("Toggle ({})", hotkey))
+        .id("toggle")
+        .build(app)?;
     let reload_item = MenuItemBuilder::new("Reload").id("reload").build(app)?;
     let open_cheatsheets_item = MenuItemBuilder::new("Open Cheatsheets Folder")
         .id("open_cheatsheets")
         .build(app)?;
+    let verbose_logging_item = CheckMenuItemBuilder::new("Verbose Logging")
+        .id("toggle_verbose_logging")
+        .checked(logging::is_verbose())
+        .build(app)?;
     let quit_item = MenuItemBuilder::new("Quit").id("quit").build(app)?;
 
-    let tray_menu = MenuBuilder::new(app)
+    let cheatsheets_submenu = build_cheatsheets_submenu(app)?;
+
+    MenuBuilder::new(app)
         .items(&[
             &toggle_item,
+            &cheatsheets_submenu,
             &reload_item,
             &open_cheatsheets_item,
+            &verbose_logging_item,
             &quit_item,
         ])
-        .build()?;
-
-    // Load tray icon from src-tauri/icons/tray.png; fall back to default app icon if missing.
-    let icon = load_tray_icon(app);
+        .build()
+}
 
-    let mut tray_builder = TrayIconBuilder::new().tooltip("Cheatrs");
-    if let Some(icon) = icon {
-        tray_builder = tray_builder.icon(icon);
+/// Rebuild the tray's menu from the current sheets/hotkey and swap it onto
+/// the live tray icon, so picking "Reload" picks up newly added JSON files
+/// (and any hotkey change) without restarting the app.
+fn rebuild_tray_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_tray_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => log::warn!("tray: failed to rebuild menu: {}", e),
     }
+}
 
-    tray_builder
-        .menu(&tray_menu)
-        .on_menu_event(|app_handle, event| match event.id().as_ref() {
-            "toggle" => toggle_main_window_visibility(app_handle),
-            "reload" => reload_main_window(app_handle),
-            "open_cheatsheets" => open_cheatsheets_folder(app_handle),
-            "quit" => app_handle.exit(0),
-            _ => {}
-        })
-        .build(app)?;
+/// The "Cheatsheets" submenu: one checkable item per sheet `load_cheatsheets`
+/// finds, checking whichever one `get_initial_sheet_id` currently resolves
+/// to. A single disabled placeholder item stands in when none are found, so
+/// the submenu is never left empty.
+fn build_cheatsheets_submenu(app: &AppHandle) -> tauri::Result<tauri::menu::Submenu> {
+    let sheets = load_cheatsheets(app.clone()).unwrap_or_default();
+    let active_id = get_initial_sheet_id();
+
+    let mut builder = SubmenuBuilder::new(app, "Cheatsheets");
+
+    if sheets.is_empty() {
+        let empty_item = MenuItemBuilder::new("No cheatsheets found")
+            .id("no_cheatsheets")
+            .enabled(false)
+            .build(app)?;
+        builder = builder.item(&empty_item);
+    } else {
+        for sheet in &sheets {
+            let checked = active_id.as_deref() == Some(sheet.id.as_str());
+            let item = CheckMenuItemBuilder::new(&sheet.name)
+                .id(format!("sheet:{}", sheet.id))
+                .checked(checked)
+                .build(app)?;
+            builder = builder.item(&item);
+        }
+    }
 
-    Ok(())
+    builder.build()
 }
 
 /// Load embedded tray icon.
@@ -458,27 +785,40 @@ fn load_tray_icon<R: Runtime>(_app: &tauri::App<R>) -> Option<tauri::image::Imag
     match Image::from_bytes(TRAY_ICON_BYTES) {
         Ok(img) => Some(img),
         Err(e) => {
-            eprintln!("tray icon: failed to load embedded icon: {}", e);
+            log::warn!("tray icon: failed to load embedded icon: {}", e);
             None
         }
     }
 }
 
-/// Register global hotkey (Windows, macOS, Linux) using the plugin.
+/// Register the configured global hotkey (Windows, macOS, Linux) using the
+/// plugin. Falls back to the default accelerator if the saved one is no
+/// longer valid (e.g. a hand-edited config file), so a bad config can't
+/// brick the app at startup.
 #[cfg(desktop)]
 fn setup_global_shortcut<R: tauri::Runtime>(app: &mut tauri::App<R>) -> tauri::Result<()> {
-    use tauri_plugin_global_shortcut::{Code, Modifiers, ShortcutState};
+    use tauri_plugin_global_shortcut::ShortcutState;
+
+    let configured = load_config().global_shortcut;
+    let shortcut = if tauri_plugin_global_shortcut::Shortcut::try_from(configured.as_str()).is_ok()
+    {
+        configured
+    } else {
+        log::warn!(
+            "global-shortcut: \"{}\" is invalid, falling back to the default",
+            configured
+        );
+        default_global_shortcut()
+    };
 
     let plugin = tauri_plugin_global_shortcut::Builder::new()
-        .with_shortcuts(["super+slash"])
+        .with_shortcuts([shortcut.as_str()])
         .map_err(|e| {
             tauri::Error::PluginInitialization("global-shortcut".to_string(), e.to_string())
         })?
-        .with_handler(|app_handle, shortcut, event| {
+        .with_handler(|app_handle, _shortcut, event| {
             if event.state == ShortcutState::Pressed {
-                if shortcut.matches(Modifiers::SUPER, Code::Slash) {
-                    toggle_main_window_visibility(app_handle);
-                }
+                toggle_main_window_visibility(app_handle);
             }
         })
         .build();
@@ -488,20 +828,79 @@ fn setup_global_shortcut<R: tauri::Runtime>(app: &mut tauri::App<R>) -> tauri::R
     Ok(())
 }
 
+/// Unregister the current global hotkey and register `accelerator` in its
+/// place, persisting it on success. On failure (unparsable accelerator, or
+/// one already claimed by another application) the previous binding is
+/// re-registered so the user is never locked out of toggling the window.
+#[tauri::command]
+fn set_global_shortcut<R: Runtime>(app: AppHandle<R>, accelerator: String) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let manager = app.global_shortcut();
+    let previous = load_config().global_shortcut;
+
+    let _ = manager.unregister(previous.as_str());
+
+    if let Err(e) = manager.register(accelerator.as_str()) {
+        let _ = manager.register(previous.as_str());
+        return Err(format!(
+            "\"{}\" is not a valid or available shortcut: {}",
+            accelerator, e
+        ));
+    }
+
+    let mut config = load_config();
+    config.global_shortcut = accelerator;
+    save_config(&config)
+}
+
+/// Persist `window`'s current geometry to `window-state.json`, logging (and
+/// otherwise ignoring) any failure the way the rest of this module does.
+fn capture_window_state<R: Runtime>(window: &tauri::WebviewWindow<R>) {
+    match config_dir() {
+        Ok(dir) => {
+            if let Err(e) = window_state::capture(window, &dir) {
+                log::warn!("window-state: failed to capture: {}", e);
+            }
+        }
+        Err(e) => log::warn!("window-state: failed to capture: {}", e),
+    }
+}
+
+/// Hide `window`, capturing its geometry first so it can be restored next
+/// time it's shown.
+fn hide_and_capture<R: Runtime>(window: &tauri::WebviewWindow<R>) {
+    capture_window_state(window);
+    let _ = window.hide();
+}
+
 /// Toggle main window visibility. Use SW_HIDE semantics under the hood.
 fn toggle_main_window_visibility<R: Runtime>(app: &AppHandle<R>) {
     if let Some(window) = app.get_webview_window("main") {
         match window.is_visible() {
             Ok(true) => {
-                let _ = window.hide(); // completely hide (SW_HIDE-equivalent)
+                hide_and_capture(&window); // completely hide (SW_HIDE-equivalent)
             }
             Ok(false) | Err(_) => {
                 // Capture the active process BEFORE showing the window
                 let process = get_active_process_name();
                 *CAPTURED_PROCESS.lock().unwrap() = process;
 
-                // Set window size based on screen size before showing
-                let _ = set_window_size_from_screen(window.clone());
+                // Restore the last saved geometry; fall back to the
+                // 80%-and-center default on first run or when the saved
+                // rect no longer fits any connected monitor.
+                let restored = config_dir()
+                    .ok()
+                    .and_then(|dir| window_state::load(&dir))
+                    .map(|state| {
+                        window_state::restore(&window, &state, window_state::ALL).unwrap_or(false)
+                    })
+                    .unwrap_or(false);
+                if !restored {
+                    let _ = set_window_size_from_screen(window.clone());
+                }
+
+                let _ = window.set_visible_on_all_workspaces(load_config().visible_on_all_workspaces);
                 let _ = window.show();
                 let _ = window.set_focus();
 
@@ -551,7 +950,7 @@ fn open_cheatsheets_folder<R: Runtime>(
                     // ShellExecute returns > 32 on success
                     let result_code = result.0 as isize;
                     if result_code <= 32 {
-                        eprintln!(
+                        log::warn!(
                             "Failed to open folder with ShellExecute: error code {}",
                             result_code
                         );
@@ -567,7 +966,7 @@ fn open_cheatsheets_folder<R: Runtime>(
 
             if let Some(dir_str) = dir.to_str() {
                 if let Err(e) = app.opener().open_path(dir_str, None::<&str>) {
-                    eprintln!("Failed to open cheatsheets folder: {}", e);
+                    log::warn!("Failed to open cheatsheets folder: {}", e);
                 }
             }
         }
@@ -623,13 +1022,22 @@ fn apply_toolwindow_style<R: Runtime>(window: &tauri::WebviewWindow<R>) {
     }
 }
 
+/// Apply the saved `visible_on_all_workspaces` preference to the main
+/// window at startup, so macOS Spaces/Linux virtual desktops already have
+/// the HUD following the active workspace before the user ever toggles it.
+fn apply_visible_on_all_workspaces<R: Runtime>(app: &tauri::App<R>) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.set_visible_on_all_workspaces(load_config().visible_on_all_workspaces);
+    }
+}
+
 /// Setup blur event handler to hide the window when it loses focus.
 fn setup_blur_handler<R: Runtime>(app: &mut tauri::App<R>) -> tauri::Result<()> {
     if let Some(window) = app.get_webview_window("main") {
         window.clone().listen("tauri://blur", move |_| {
             // Hide the window when it loses focus
             if let Some(app_window) = window.app_handle().get_webview_window("main") {
-                let _ = app_window.hide();
+                hide_and_capture(&app_window);
             }
         });
     }
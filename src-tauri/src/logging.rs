@@ -0,0 +1,124 @@
+//! File+stderr backend for the `log` facade, replacing the scattered
+//! `eprintln!` diagnostics that used to vanish in release builds. The base
+//! level comes from the `RUST_LOG` env var (falling back to `Info`), and is
+//! bumped to at least `Debug` while verbose mode is on (see [`set_verbose`]),
+//! so a user filing a bug report can turn up detail without restarting with
+//! a different env var. The log file is rotated to a single `.old` backup
+//! once it's grown past [`MAX_LOG_BYTES`] by the time [`init`] runs.
+
+use log::{LevelFilter, Metadata, Record};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+const LOG_FILE_NAME: &str = "cheatrs.log";
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+struct FileAndStderrLogger {
+    base_level: LevelFilter,
+    file: Mutex<Option<File>>,
+}
+
+impl FileAndStderrLogger {
+    fn effective_level(&self) -> LevelFilter {
+        if VERBOSE.load(Ordering::Relaxed) {
+            self.base_level.max(LevelFilter::Debug)
+        } else {
+            self.base_level
+        }
+    }
+}
+
+impl log::Log for FileAndStderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.effective_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!(
+            "[{}] {}: {}\n",
+            record.level(),
+            record.target(),
+            record.args()
+        );
+
+        eprint!("{}", line);
+
+        if let Ok(mut file) = self.file.lock() {
+            if let Some(file) = file.as_mut() {
+                let _ = file.write_all(line.as_bytes());
+                let _ = file.flush();
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            if let Some(file) = file.as_mut() {
+                let _ = file.flush();
+            }
+        }
+    }
+}
+
+fn log_path(config_dir: &Path) -> PathBuf {
+    config_dir.join(LOG_FILE_NAME)
+}
+
+/// Rename `cheatrs.log` to `cheatrs.log.old` (overwriting any previous
+/// backup) if it's already grown past [`MAX_LOG_BYTES`], then open (or
+/// create) the live log file for appending.
+fn open_log_file(config_dir: &Path) -> Option<File> {
+    let path = log_path(config_dir);
+
+    if let Ok(metadata) = std::fs::metadata(&path) {
+        if metadata.len() > MAX_LOG_BYTES {
+            let backup_path = config_dir.join(format!("{}.old", LOG_FILE_NAME));
+            let _ = std::fs::rename(&path, backup_path);
+        }
+    }
+
+    OpenOptions::new().create(true).append(true).open(&path).ok()
+}
+
+/// Install the logger. `config_dir` is where `cheatrs.log` lives; pass
+/// `None` to log to stderr only (e.g. if the config directory couldn't be
+/// resolved). Safe to call more than once; only the first call takes effect.
+pub fn init(config_dir: Option<&Path>) {
+    let base_level = std::env::var("RUST_LOG")
+        .ok()
+        .and_then(|value| value.parse::<LevelFilter>().ok())
+        .unwrap_or(LevelFilter::Info);
+
+    let file = config_dir.and_then(open_log_file);
+
+    let logger = FileAndStderrLogger {
+        base_level,
+        file: Mutex::new(file),
+    };
+
+    // The real filtering happens in `FileAndStderrLogger::enabled`, which
+    // reacts to `set_verbose` at runtime; the max level here just has to
+    // stay permissive enough to never mask it.
+    log::set_max_level(LevelFilter::Trace);
+    let _ = log::set_boxed_logger(Box::new(logger));
+}
+
+/// Turn verbose mode on or off (on forces at least `Debug` regardless of
+/// `RUST_LOG`), returning the new state.
+pub fn set_verbose(enabled: bool) -> bool {
+    VERBOSE.store(enabled, Ordering::Relaxed);
+    enabled
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
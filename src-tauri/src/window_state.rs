@@ -0,0 +1,129 @@
+//! Persisted window geometry, captured on blur/hide and on quit, restored
+//! the next time the overlay is shown. Modeled on the `StateFlags` idea
+//! from the community window-state plugin: a bitfield selects which
+//! aspects of a saved [`WindowState`] actually get applied, so e.g. a
+//! maximized flag can be ignored without discarding the saved size too.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{PhysicalPosition, PhysicalSize, Runtime, WebviewWindow};
+
+pub type StateFlags = u8;
+pub const SIZE: StateFlags = 0b001;
+pub const POSITION: StateFlags = 0b010;
+pub const MAXIMIZED: StateFlags = 0b100;
+pub const ALL: StateFlags = SIZE | POSITION | MAXIMIZED;
+
+/// Captured window geometry in physical pixels (so restoring never has to
+/// re-derive a scale factor), plus the monitor it was captured on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowState {
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,
+    pub y: i32,
+    pub maximized: bool,
+    pub monitor_name: Option<String>,
+}
+
+fn state_path(config_dir: &Path) -> PathBuf {
+    config_dir.join("window-state.json")
+}
+
+/// Load the last-saved window state, if any was ever captured.
+pub fn load(config_dir: &Path) -> Option<WindowState> {
+    let content = fs::read_to_string(state_path(config_dir)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save(config_dir: &Path, state: &WindowState) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(state_path(config_dir), content).map_err(|e| e.to_string())
+}
+
+/// Capture `window`'s current outer size/position/maximized flag and
+/// persist it to `config_dir`.
+pub fn capture<R: Runtime>(window: &WebviewWindow<R>, config_dir: &Path) -> Result<(), String> {
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    let maximized = window.is_maximized().map_err(|e| e.to_string())?;
+    let monitor_name = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|monitor| monitor.name().cloned());
+
+    save(
+        config_dir,
+        &WindowState {
+            width: size.width,
+            height: size.height,
+            x: position.x,
+            y: position.y,
+            maximized,
+            monitor_name,
+        },
+    )
+}
+
+/// Whether `state`'s saved rect still overlaps at least one of `window`'s
+/// currently connected monitors, so a monitor that's been unplugged (or a
+/// rect that's since scrolled fully off-screen) is detected correctly.
+fn fits_a_monitor<R: Runtime>(window: &WebviewWindow<R>, state: &WindowState) -> bool {
+    let Ok(monitors) = window.available_monitors() else {
+        return false;
+    };
+
+    monitors.iter().any(|monitor| {
+        let pos = monitor.position();
+        let size = monitor.size();
+        let left = pos.x;
+        let top = pos.y;
+        let right = pos.x + size.width as i32;
+        let bottom = pos.y + size.height as i32;
+
+        state.x < right
+            && state.x + state.width as i32 > left
+            && state.y < bottom
+            && state.y + state.height as i32 > top
+    })
+}
+
+/// Apply the aspects of `state` selected by `flags` to `window`. Returns
+/// `Ok(false)` without changing anything when the saved rect no longer
+/// fits any connected monitor, so the caller can fall back to the default
+/// 80%-and-center placement instead.
+pub fn restore<R: Runtime>(
+    window: &WebviewWindow<R>,
+    state: &WindowState,
+    flags: StateFlags,
+) -> Result<bool, String> {
+    if !fits_a_monitor(window, state) {
+        return Ok(false);
+    }
+
+    if flags & POSITION != 0 {
+        window
+            .set_position(tauri::Position::Physical(PhysicalPosition {
+                x: state.x,
+                y: state.y,
+            }))
+            .map_err(|e| e.to_string())?;
+    }
+
+    if flags & SIZE != 0 {
+        window
+            .set_size(tauri::Size::Physical(PhysicalSize {
+                width: state.width,
+                height: state.height,
+            }))
+            .map_err(|e| e.to_string())?;
+    }
+
+    if flags & MAXIMIZED != 0 && state.maximized {
+        window.maximize().map_err(|e| e.to_string())?;
+    }
+
+    Ok(true)
+}
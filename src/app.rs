@@ -3,13 +3,20 @@ use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use web_sys::{KeyboardEvent, window};
 
+mod fuzzy;
+mod highlight;
+mod query;
+use fuzzy::{char_bag, highlight_positions, typo_budget};
+use highlight::HighlightedText;
+use query::{Query, best_fuzzy_match, parse_query, score_query, term_variants};
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
     fn log(s: &str);
 
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
-    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+    pub(crate) async fn invoke(cmd: &str, args: JsValue) -> JsValue;
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -20,6 +27,10 @@ struct CheatItem {
     tags: Vec<String>,
     #[serde(default)]
     hint: Option<String>,
+    /// Grammar hint (e.g. `"bash"`, `"vim"`, `"regex"`) used to syntax
+    /// highlight `keys`/`hint`. Absent/unknown means no highlighting.
+    #[serde(default)]
+    lang: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -38,6 +49,12 @@ struct CheatSheet {
     hint: Option<String>,
     #[serde(default)]
     processes: Vec<String>,
+    /// Groups of interchangeable words (e.g. `["delete", "remove", "cut"]`)
+    /// so a query for one also matches entries using another. Empty by
+    /// default, in which case search behaves exactly as if synonyms didn't
+    /// exist.
+    #[serde(default)]
+    synonyms: Vec<Vec<String>>,
     sections: Vec<CheatSection>,
 }
 
@@ -49,47 +66,63 @@ struct AppConfig {
     last_cheatsheet_per_process: std::collections::HashMap<String, String>,
     #[serde(default)]
     search_all_for_process: bool,
+    #[serde(default = "default_rank_criteria")]
+    rank_criteria: Vec<RankCriterion>,
 }
 
-fn fuzzy_score(query: &str, text: &str) -> f64 {
-    let query = query.to_lowercase();
-    let text = text.to_lowercase();
-
-    let mut qi = 0;
-    let mut ti = 0;
-    let mut score = 0.0;
-
-    let query_chars: Vec<char> = query.chars().collect();
-    let text_chars: Vec<char> = text.chars().collect();
+/// Tie-breaker used to order search results, modeled on skim's `RankCriteria`.
+/// Comparison walks a list of these in order and stops at the first
+/// non-equal key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RankCriterion {
+    /// Descending fuzzy match score.
+    Score,
+    /// Prefer entries with shorter text.
+    Length,
+    /// Prefer matches that start earlier in the text.
+    Begin,
+    /// Stable original order.
+    Index,
+}
 
-    while qi < query_chars.len() {
-        let qc = query_chars[qi];
-        if qc == ' ' {
-            qi += 1;
-            score += 0.5;
-            continue;
-        }
+fn default_rank_criteria() -> Vec<RankCriterion> {
+    vec![
+        RankCriterion::Score,
+        RankCriterion::Begin,
+        RankCriterion::Length,
+        RankCriterion::Index,
+    ]
+}
 
-        if let Some(idx) = text_chars[ti..].iter().position(|&c| c == qc) {
-            let actual_idx = ti + idx;
-            score += 1.0;
-            if actual_idx == ti {
-                score += 1.0;
-            }
-            if actual_idx == 0
-                || text_chars[actual_idx - 1].is_whitespace()
-                || !text_chars[actual_idx - 1].is_alphanumeric()
-            {
-                score += 0.5;
-            }
-            ti = actual_idx + 1;
-        } else {
-            return 0.0;
-        }
-        qi += 1;
+/// Named presets offered by the "Sort by" toolbar control. Each still falls
+/// back through the remaining criteria so ties stay deterministic.
+fn rank_criteria_preset(name: &str) -> Vec<RankCriterion> {
+    match name {
+        "begin" => vec![
+            RankCriterion::Begin,
+            RankCriterion::Score,
+            RankCriterion::Length,
+            RankCriterion::Index,
+        ],
+        "length" => vec![
+            RankCriterion::Length,
+            RankCriterion::Score,
+            RankCriterion::Begin,
+            RankCriterion::Index,
+        ],
+        _ => default_rank_criteria(),
     }
+}
 
-    score / (1.0 + text_chars.len() as f64 / 120.0)
+/// Inverse of [`rank_criteria_preset`], used to reflect the persisted
+/// criteria back into the `<select>`'s value.
+fn rank_criteria_preset_name(criteria: &[RankCriterion]) -> &'static str {
+    match criteria.first() {
+        Some(RankCriterion::Begin) => "begin",
+        Some(RankCriterion::Length) => "length",
+        _ => "score",
+    }
 }
 
 async fn load_sheets() -> Vec<CheatSheet> {
@@ -118,6 +151,9 @@ struct IndexEntryWithTags {
     item_index: usize,
     text: String,
     tags_text: String,
+    /// Character-presence bitmask of `text`, precomputed so `search_matches`
+    /// can cheaply rule out entries before scoring them.
+    char_bag: u64,
 }
 
 fn build_index(
@@ -160,13 +196,16 @@ fn build_index(
                 parts.push(sheet.description.clone());
 
                 let tags_text = item.tags.join(" ").to_lowercase();
+                let text = parts.join(" ").to_lowercase();
+                let char_bag = char_bag(&text);
 
                 index.push(IndexEntryWithTags {
                     sheet_id: sheet.id.clone(),
                     section_index,
                     item_index,
-                    text: parts.join(" ").to_lowercase(),
+                    text,
                     tags_text,
+                    char_bag,
                 });
             }
         }
@@ -175,6 +214,83 @@ fn build_index(
     index
 }
 
+/// One ranked search hit, carrying everything [`compare_matches`] needs to
+/// break ties between `RankCriterion`s without re-deriving them.
+#[derive(Debug, Clone, PartialEq)]
+struct SearchMatch {
+    /// Id of the sheet this hit came from, which may differ from the
+    /// current sheet when global cross-sheet search is on.
+    sheet_id: String,
+    section_index: usize,
+    item_index: usize,
+    score: f64,
+    /// Index of the first matched character, used by `RankCriterion::Begin`.
+    begin: usize,
+    /// Length of the entry's indexed text, used by `RankCriterion::Length`.
+    length: usize,
+    /// Position in the original index, used by `RankCriterion::Index`.
+    order: usize,
+}
+
+/// Result of running a query: the ranked hits actually shown (capped for
+/// render performance) alongside the untruncated count, so the UI can still
+/// report how many entries really matched.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct SearchResults {
+    matches: Vec<SearchMatch>,
+    total: usize,
+}
+
+/// How much weight a semantic similarity score carries when blended with
+/// the lexical match score.
+const SEMANTIC_BLEND_WEIGHT: f64 = 8.0;
+/// Minimum cosine similarity for a semantic-only hit (no lexical match at
+/// all) to be surfaced.
+const SEMANTIC_MATCH_THRESHOLD: f64 = 0.35;
+
+/// Order two matches by walking `criteria` and returning at the first
+/// non-equal key, falling back to `Ordering::Equal` if every criterion ties.
+fn compare_matches(a: &SearchMatch, b: &SearchMatch, criteria: &[RankCriterion]) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    for criterion in criteria {
+        let ordering = match criterion {
+            RankCriterion::Score => b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal),
+            RankCriterion::Length => a.length.cmp(&b.length),
+            RankCriterion::Begin => a.begin.cmp(&b.begin),
+            RankCriterion::Index => a.order.cmp(&b.order),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// Render `text` with the runs matched by `query`'s fuzzy terms (tried
+/// against `text` with `synonyms`) wrapped in `<mark>`. Highlights the
+/// best-scoring term's positions rather than re-running the raw query
+/// string, so it still lights up something for multi-term/operator queries
+/// like `copy !mouse ^git`.
+fn render_marked(query: &Query, synonyms: &[Vec<String>], text: &str) -> impl IntoView {
+    let Some(m) = best_fuzzy_match(query, text, synonyms) else {
+        return text.to_string().into_any();
+    };
+
+    highlight_positions(text, &m.positions)
+        .into_iter()
+        .map(|(run, is_match)| {
+            if is_match {
+                view! { <mark>{run}</mark> }.into_any()
+            } else {
+                run.into_any()
+            }
+        })
+        .collect::<Vec<_>>()
+        .into_any()
+}
+
 #[component]
 pub fn App() -> impl IntoView {
     let (sheets, set_sheets) = signal(Vec::<CheatSheet>::new());
@@ -183,6 +299,11 @@ pub fn App() -> impl IntoView {
     let (show_tags, set_show_tags) = signal(false);
     let (current_process, set_current_process) = signal(Option::<String>::None);
     let (search_all_for_process, set_search_all_for_process) = signal(true);
+    let (rank_criteria, set_rank_criteria) = signal(default_rank_criteria());
+    let (semantic_enabled, set_semantic_enabled) = signal(false);
+    let (semantic_scores, set_semantic_scores) =
+        signal(std::collections::HashMap::<(String, usize, usize), f64>::new());
+    let (global_search, set_global_search) = signal(false);
 
     // Load sheets and initial config on mount
     Effect::new(move || {
@@ -200,6 +321,7 @@ pub fn App() -> impl IntoView {
             if !config_result.is_undefined() && !config_result.is_null() {
                 if let Ok(config) = serde_wasm_bindgen::from_value::<AppConfig>(config_result) {
                     set_search_all_for_process.set(config.search_all_for_process);
+                    set_rank_criteria.set(config.rank_criteria);
                 }
             }
 
@@ -227,6 +349,13 @@ pub fn App() -> impl IntoView {
                 set_current_sheet_id.set(first_sheet.id.clone());
             }
 
+            // Build/refresh the semantic embedding index in the background;
+            // the cache file no-ops when the content hash hasn't changed.
+            let args = serde_json::json!({ "sheets": loaded_sheets });
+            if let Ok(js_args) = serde_wasm_bindgen::to_value(&args) {
+                let _ = invoke("build_embedding_index", js_args).await;
+            }
+
             set_sheets.set(loaded_sheets);
         });
     });
@@ -314,6 +443,30 @@ pub fn App() -> impl IntoView {
         });
     });
 
+    // Switch sheets when one is picked from the tray's "Cheatsheets" submenu
+    Effect::new(move || {
+        use wasm_bindgen::JsCast;
+
+        #[wasm_bindgen]
+        extern "C" {
+            #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "event"])]
+            async fn listen(event: &str, handler: &js_sys::Function) -> JsValue;
+        }
+
+        leptos::task::spawn_local(async move {
+            let handler = Closure::<dyn Fn(JsValue)>::new(move |event: JsValue| {
+                if let Ok(payload) = js_sys::Reflect::get(&event, &JsValue::from_str("payload")) {
+                    if let Ok(sheet_id) = serde_wasm_bindgen::from_value::<String>(payload) {
+                        set_current_sheet_id.set(sheet_id);
+                    }
+                }
+            });
+
+            let _ = listen("switch-cheatsheet", handler.as_ref().unchecked_ref()).await;
+            handler.forget();
+        });
+    });
+
     // Save last cheatsheet when it changes
     Effect::new(move || {
         let sheet_id = current_sheet_id.get();
@@ -358,6 +511,183 @@ pub fn App() -> impl IntoView {
         sheets.get().into_iter().find(|s| s.id == sheet_id)
     });
 
+    // Flattened, ranked search hits for the current query, from the current
+    // sheet or, with global search on, from every indexed sheet. Shared
+    // by the results view and the keyboard navigation below so that arrow
+    // keys and Enter act on exactly what's on screen.
+    let search_matches = Memo::new(move |_| -> SearchResults {
+        let Some(sheet) = current_sheet.get() else {
+            return SearchResults::default();
+        };
+        let query = search_query.get();
+        if query.trim().is_empty() {
+            return SearchResults::default();
+        }
+
+        // A bare `#foo` query is shorthand for a single tag-scoped atom; the
+        // query grammar also accepts `#foo` as one atom among several
+        // (`copy #mouse`), so both forms go through the same scoring path.
+        let parsed_query = parse_query(&query);
+        let blend_semantic = semantic_enabled.get();
+        let semantic = semantic_scores.get();
+
+        // Cheap superset check that skips the expensive fuzzy scorer for
+        // entries that can't possibly match: a fuzzy subsequence requires
+        // every character of a term (or one of its synonym variants) to
+        // appear in the entry's text, so an entry is ruled out up front
+        // when, for some non-negated term, none of its variant bags are a
+        // subset of the entry's bag. For a fuzzy term, each variant's own
+        // typo-tolerance budget (see `query::score_query`) allows that many
+        // bits of its bag to be missing instead, since a typo can introduce
+        // a character the correct word never had.
+        let term_bags: Vec<Vec<(u64, usize)>> = parsed_query
+            .terms
+            .iter()
+            .filter(|term| !term.negate)
+            .map(|term| {
+                term_variants(term, &sheet.synonyms)
+                    .iter()
+                    .map(|variant| {
+                        let budget = if term.kind == query::MatchKind::Fuzzy {
+                            typo_budget(variant.chars().count())
+                        } else {
+                            0
+                        };
+                        (char_bag(variant), budget)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let search_all_sheets = global_search.get();
+
+        let mut matches: Vec<SearchMatch> = index
+            .get()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| search_all_sheets || entry.sheet_id == sheet.id)
+            .filter_map(|(order, entry)| {
+                let passes_bag_prefilter = term_bags.iter().all(|variant_bags| {
+                    variant_bags.iter().any(|&(variant_bag, budget)| {
+                        (variant_bag & !entry.char_bag).count_ones() as usize <= budget
+                    })
+                });
+                let lexical = if passes_bag_prefilter {
+                    score_query(&parsed_query, &entry.text, &entry.tags_text, &sheet.synonyms)
+                } else {
+                    None
+                };
+
+                let semantic_score = blend_semantic
+                    .then(|| {
+                        semantic
+                            .get(&(entry.sheet_id.clone(), entry.section_index, entry.item_index))
+                            .copied()
+                    })
+                    .flatten();
+
+                // Weighted sum of lexical and semantic scores: a short
+                // keystroke query stays dominated by its literal score,
+                // while a natural-language query with no literal match can
+                // still surface entries through semantic similarity alone.
+                let score = match (lexical, semantic_score) {
+                    (Some(l), Some(s)) => Some(l + s * SEMANTIC_BLEND_WEIGHT),
+                    (Some(l), None) => Some(l),
+                    (None, Some(s)) if s > SEMANTIC_MATCH_THRESHOLD => {
+                        Some(s * SEMANTIC_BLEND_WEIGHT)
+                    }
+                    _ => None,
+                };
+
+                score.map(|score| {
+                    let begin = best_fuzzy_match(&parsed_query, &entry.text, &sheet.synonyms)
+                        .and_then(|m| m.positions.first().copied())
+                        .unwrap_or(usize::MAX);
+                    SearchMatch {
+                        sheet_id: entry.sheet_id.clone(),
+                        section_index: entry.section_index,
+                        item_index: entry.item_index,
+                        score,
+                        begin,
+                        length: entry.text.len(),
+                        order,
+                    }
+                })
+            })
+            .collect();
+
+        let criteria = rank_criteria.get();
+        matches.sort_by(|a, b| compare_matches(a, b, &criteria));
+        let total = matches.len();
+        matches.truncate(100);
+        SearchResults { matches, total }
+    });
+
+    // Re-run the semantic search whenever the query, sheet, toggle, or
+    // global-search scope changes, so `search_matches` above always has
+    // fresh scores to blend. When `global_search` is on the backend ranks
+    // every sheet's entries together so non-current-sheet entries get a
+    // `semantic_score` too, instead of always being scoped to the current
+    // sheet alone.
+    Effect::new(move |_| {
+        let query = search_query.get();
+        let sheet_id = current_sheet_id.get();
+        let global = global_search.get();
+        if !semantic_enabled.get() || query.trim().is_empty() || sheet_id.is_empty() {
+            set_semantic_scores.set(std::collections::HashMap::new());
+            return;
+        }
+
+        leptos::task::spawn_local(async move {
+            use serde_wasm_bindgen::to_value;
+            let args = serde_json::json!({
+                "sheetId": sheet_id,
+                "query": query,
+                "topK": 50,
+                "global": global,
+            });
+            if let Ok(js_args) = to_value(&args) {
+                let result = invoke("semantic_search", js_args).await;
+                if !result.is_undefined() && !result.is_null() {
+                    if let Ok(hits) = serde_wasm_bindgen::from_value::<
+                        Vec<(String, usize, usize, f64)>,
+                    >(result)
+                    {
+                        let scores = hits
+                            .into_iter()
+                            .map(|(sheet_id, section_index, item_index, score)| {
+                                ((sheet_id, section_index, item_index), score)
+                            })
+                            .collect();
+                        set_semantic_scores.set(scores);
+                    }
+                }
+            }
+        });
+    });
+
+    let (selected, set_selected) = signal(0usize);
+
+    // Reset the selection to the top whenever the query or the sheet scope
+    // (and thus the result set) changes.
+    Effect::new(move |_| {
+        let _ = search_query.get();
+        let _ = global_search.get();
+        set_selected.set(0);
+    });
+
+    // Auto-scroll the highlighted row into view whenever the selection moves.
+    Effect::new(move |_| {
+        let idx = selected.get();
+        if let Some(document) = window().and_then(|w| w.document()) {
+            if let Some(element) = document.get_element_by_id(&format!("result-item-{idx}")) {
+                element.scroll_into_view_with_scroll_into_view_options(
+                    web_sys::ScrollIntoViewOptions::new().block(web_sys::ScrollLogicalPosition::Nearest),
+                );
+            }
+        }
+    });
+
     // Filter sheets for dropdown based on process
     let filtered_sheets = Memo::new(move |_| {
         let all_sheets = sheets.get();
@@ -388,14 +718,24 @@ pub fn App() -> impl IntoView {
         let handler = Closure::<dyn Fn(web_sys::Event)>::new(move |event: web_sys::Event| {
             let e: KeyboardEvent = event.dyn_into().unwrap();
             let target = e.target();
-            let is_input = if let Some(element) =
-                target.and_then(|t| t.dyn_into::<web_sys::HtmlElement>().ok())
-            {
+            let focused_element = target.and_then(|t| t.dyn_into::<web_sys::HtmlElement>().ok());
+            let is_input = if let Some(element) = &focused_element {
                 let tag = element.tag_name().to_lowercase();
-                tag == "input" || tag == "textarea" || element.is_content_editable()
+                tag == "input"
+                    || tag == "textarea"
+                    || tag == "select"
+                    || element.is_content_editable()
             } else {
                 false
             };
+            // Same as `is_input`, but doesn't count the search box itself —
+            // result navigation and copy-to-clipboard are meant to work
+            // while the user is still typing their query into it.
+            let is_other_control = is_input
+                && focused_element
+                    .as_ref()
+                    .map(|e| e.id() != "search-input")
+                    .unwrap_or(false);
 
             // Alt + Arrow keys to switch sheets
             if e.alt_key() && !e.ctrl_key() && !e.meta_key() && !e.shift_key() {
@@ -427,6 +767,49 @@ pub fn App() -> impl IntoView {
                 return;
             }
 
+            // Arrow Up/Down and vim-style Ctrl+J/Ctrl+K move through search
+            // results. Skipped when a form control (e.g. the rank-criteria
+            // `<select>`) has focus, so its own native arrow-key navigation
+            // isn't hijacked.
+            let is_next = e.key() == "ArrowDown" || (e.ctrl_key() && e.key() == "j");
+            let is_prev = e.key() == "ArrowUp" || (e.ctrl_key() && e.key() == "k");
+            if !is_other_control && !e.alt_key() && (is_next || is_prev) {
+                let results = search_matches.get().matches;
+                if !results.is_empty() {
+                    let current = selected.get();
+                    let next = if is_next {
+                        (current + 1).min(results.len() - 1)
+                    } else {
+                        current.saturating_sub(1)
+                    };
+                    set_selected.set(next);
+                    e.prevent_default();
+                    return;
+                }
+            }
+
+            // Enter copies the selected result's keys to the clipboard.
+            if !is_other_control && e.key() == "Enter" {
+                let results = search_matches.get().matches;
+                if let Some(m) = results.get(selected.get()) {
+                    let hit_sheet = sheets.get().into_iter().find(|s| s.id == m.sheet_id);
+                    if let Some(sheet) = hit_sheet {
+                        let keys = sheet.sections[m.section_index].items[m.item_index]
+                            .keys
+                            .join(" ");
+                        leptos::task::spawn_local(async move {
+                            use serde_wasm_bindgen::to_value;
+                            let args = serde_json::json!({ "text": keys });
+                            if let Ok(js_args) = to_value(&args) {
+                                let _ = invoke("copy_to_clipboard", js_args).await;
+                            }
+                        });
+                    }
+                    e.prevent_default();
+                    return;
+                }
+            }
+
             // Focus search on any key press
             if !is_input && !e.meta_key() && !e.ctrl_key() && !e.alt_key() {
                 if e.key().len() == 1
@@ -512,6 +895,29 @@ pub fn App() -> impl IntoView {
                         "Show tags"
                     </label>
 
+                    <label>
+                        "Sort by"
+                        <select
+                            id="rank-criteria-select"
+                            on:change=move |ev| {
+                                let criteria = rank_criteria_preset(&event_target_value(&ev));
+                                set_rank_criteria.set(criteria.clone());
+                                leptos::task::spawn_local(async move {
+                                    use serde_wasm_bindgen::to_value;
+                                    let args = serde_json::json!({ "criteria": criteria });
+                                    if let Ok(js_args) = to_value(&args) {
+                                        let _ = invoke("set_rank_criteria", js_args).await;
+                                    }
+                                });
+                            }
+                            prop:value=move || rank_criteria_preset_name(&rank_criteria.get())
+                        >
+                            <option value="score">"Best match"</option>
+                            <option value="begin">"Earliest match"</option>
+                            <option value="length">"Shortest entry"</option>
+                        </select>
+                    </label>
+
                     <label>
                         <input
                             type="checkbox"
@@ -534,6 +940,32 @@ pub fn App() -> impl IntoView {
                             }
                         }}
                     </label>
+
+                    <label>
+                        <input
+                            type="checkbox"
+                            role="switch"
+                            id="toggle-semantic-search"
+                            on:change=move |ev| {
+                                set_semantic_enabled.set(event_target_checked(&ev));
+                            }
+                            prop:checked=move || semantic_enabled.get()
+                        />
+                        "Semantic search"
+                    </label>
+
+                    <label>
+                        <input
+                            type="checkbox"
+                            role="switch"
+                            id="toggle-global-search"
+                            on:change=move |ev| {
+                                set_global_search.set(event_target_checked(&ev));
+                            }
+                            prop:checked=move || global_search.get()
+                        />
+                        "Search all sheets"
+                    </label>
                 </div>
             </div>
 
@@ -563,12 +995,17 @@ pub fn App() -> impl IntoView {
                                                         let item_desc = item.desc.clone();
                                                         let item_tags = item.tags.clone();
                                                         let item_hint = item.hint.clone();
+                                                        let item_lang = item.lang.clone();
                                                         view! {
                                                             <div class="cheat-item">
                                                                 <div class="key-chips">
                                                                     {item_keys.iter().map(|key| {
-                                                                        let k = key.clone();
-                                                                        view! { <code class="key-chip">{k}</code> }
+                                                                        let lang = item_lang.clone();
+                                                                        view! {
+                                                                            <code class="key-chip">
+                                                                                <HighlightedText text=key.clone() lang=lang />
+                                                                            </code>
+                                                                        }
                                                                     }).collect::<Vec<_>>()}
                                                                 </div>
                                                                 <div>{item_desc}</div>
@@ -596,7 +1033,11 @@ pub fn App() -> impl IntoView {
                                                                     }
                                                                 }}
                                                                 {item_hint.map(|hint| {
-                                                                    view! { <small class="item-hint">{hint}</small> }
+                                                                    view! {
+                                                                        <small class="item-hint">
+                                                                            <HighlightedText text=hint lang=item_lang.clone() />
+                                                                        </small>
+                                                                    }
                                                                 })}
                                                             </div>
                                                         }
@@ -608,43 +1049,14 @@ pub fn App() -> impl IntoView {
                                 </div>
                             }.into_any()
                         } else {
-                            // Search results
-                            // Check if query is a tag search (starts with #)
-                            let is_tag_search = query.starts_with('#');
-                            let tag_query = if is_tag_search {
-                                query.trim_start_matches('#')
-                            } else {
-                                &query
-                            };
-
-                            let mut matches: Vec<(usize, usize, f64)> = index.get()
-                                .iter()
-                                .filter(|entry| entry.sheet_id == sheet.id)
-                                .filter_map(|entry| {
-                                    let score = if is_tag_search {
-                                        // For tag searches, prioritize tag matches
-                                        let tag_score = fuzzy_score(tag_query, &entry.tags_text);
-                                        if tag_score > 0.0 {
-                                            tag_score * 2.0 // Boost tag matches
-                                        } else {
-                                            // Also search in everything else
-                                            fuzzy_score(&query, &entry.text) * 0.5
-                                        }
-                                    } else {
-                                        fuzzy_score(&query, &entry.text)
-                                    };
-
-                                    if score > 0.0 {
-                                        Some((entry.section_index, entry.item_index, score))
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect();
-
-                            matches.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
-                            let match_count = matches.len();
-                            matches.truncate(100);
+                            // Search results, already flattened/ranked by the
+                            // `search_matches` memo shared with keyboard navigation.
+                            let results = search_matches.get();
+                            let matches = results.matches;
+                            let match_count = results.total;
+                            let all_sheets = sheets.get();
+                            let show_sheet_name = global_search.get();
+                            let parsed_query = parse_query(&query);
 
                             view! {
                                 <div>
@@ -654,28 +1066,53 @@ pub fn App() -> impl IntoView {
                                     {if matches.is_empty() {
                                         view! { <p>"No matches."</p> }.into_any()
                                     } else {
-                                        matches.into_iter().map(|(sec_idx, item_idx, score)| {
-                                            let section = &sheet.sections[sec_idx];
-                                            let item = &section.items[item_idx];
+                                        matches.into_iter().enumerate().map(|(i, m)| {
+                                            let hit_sheet = all_sheets
+                                                .iter()
+                                                .find(|s| s.id == m.sheet_id)
+                                                .unwrap_or(&sheet);
+                                            let section = &hit_sheet.sections[m.section_index];
+                                            let item = &section.items[m.item_index];
                                             let section_title = section.title.clone();
+                                            let sheet_name = hit_sheet.name.clone();
                                             let item_keys = item.keys.clone();
                                             let item_desc = item.desc.clone();
                                             let item_tags = item.tags.clone();
                                             let item_hint = item.hint.clone();
+                                            let item_lang = item.lang.clone();
+                                            let row_class = move || if selected.get() == i {
+                                                "search-item selected"
+                                            } else {
+                                                "search-item"
+                                            };
                                             view! {
-                                                <article class="search-item">
+                                                <article
+                                                    id=format!("result-item-{i}")
+                                                    class=row_class
+                                                    on:click=move |_| set_selected.set(i)
+                                                >
                                                     <header class="search-path">
+                                                        {show_sheet_name.then(|| view! {
+                                                            <span class="search-sheet">{sheet_name}</span>
+                                                        })}
                                                         <strong>{section_title}</strong>
-                                                        <small class="search-score">{format!("{:.2}", score)}</small>
+                                                        <small class="search-score">{format!("{:.2}", m.score)}</small>
                                                     </header>
                                                     <div class="cheat-item">
                                                         <div class="key-chips">
                                                             {item_keys.iter().map(|key| {
-                                                                let k = key.clone();
-                                                                view! { <code class="key-chip">{k}</code> }
+                                                                let lang = item_lang.clone();
+                                                                let positions = best_fuzzy_match(&parsed_query, key, &hit_sheet.synonyms)
+                                                                    .map(|m| m.positions)
+                                                                    .unwrap_or_default();
+                                                                view! {
+                                                                    <code class="key-chip">
+                                                                        <HighlightedText text=key.clone() lang=lang positions=positions />
+                                                                    </code>
+                                                                }
                                                             }).collect::<Vec<_>>()}
                                                         </div>
-                                                        <div>{item_desc}</div>
+                                                        <div>{render_marked(&parsed_query, &hit_sheet.synonyms, &item_desc)}</div>
                                                         {move || {
                                                             if show_tags.get() && !item_tags.is_empty() {
                                                                 view! {
@@ -700,7 +1137,11 @@ pub fn App() -> impl IntoView {
                                                             }
                                                         }}
                                                         {item_hint.map(|hint| {
-                                                            view! { <small class="item-hint">{hint}</small> }
+                                                            view! {
+                                                                <small class="item-hint">
+                                                                    <HighlightedText text=hint lang=item_lang.clone() />
+                                                                </small>
+                                                            }
                                                         })}
                                                     </div>
                                                 </article>
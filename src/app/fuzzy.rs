@@ -0,0 +1,337 @@
+//! fzf-style fuzzy matcher.
+//!
+//! Scores a query against a candidate text using a Smith-Waterman-style
+//! local alignment so that, unlike a simple greedy scan, it can also report
+//! *which* characters in the text matched. The UI uses those positions to
+//! highlight the matched substring in each `CheatItem`.
+//!
+//! Also exposes a bounded Levenshtein distance (`bounded_edit_distance`,
+//! `best_word_edit_distance`) used by `query` as a typo-tolerant fallback
+//! when a term doesn't occur as a fuzzy subsequence at all.
+
+const SCORE_MATCH: f64 = 16.0;
+const SCORE_GAP_START: f64 = 3.0;
+const SCORE_GAP_EXTENSION: f64 = 1.0;
+const BONUS_BOUNDARY: f64 = 8.0;
+const BONUS_CONSECUTIVE: f64 = 4.0;
+
+const NEG_INFINITY: f64 = f64::NEG_INFINITY;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    pub score: f64,
+    pub positions: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dir {
+    /// Start of the alignment: `query[i]` matched here with nothing before it.
+    Start,
+    /// `query[i-1]` matched at `text[j-1]` (no gap).
+    Diag,
+    /// Reached via a gap in `text` before this match.
+    Gap,
+}
+
+/// Whether `text[idx]` sits on a "word boundary": the start of the string,
+/// right after whitespace/punctuation, or a lowercase->uppercase camelCase
+/// transition.
+fn is_boundary(text: &[char], idx: usize) -> bool {
+    if idx == 0 {
+        return true;
+    }
+    let prev = text[idx - 1];
+    let cur = text[idx];
+    if prev.is_whitespace() || !prev.is_alphanumeric() {
+        return true;
+    }
+    prev.is_lowercase() && cur.is_uppercase()
+}
+
+/// Score `query` as a fuzzy subsequence of `text`, returning the best
+/// alignment's score plus the matched character indices (sorted ascending),
+/// or `None` if `query` isn't a subsequence of `text` at all.
+///
+/// An empty query always matches with a score of 0 and no positions.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let lower_text: Vec<char> = text.to_lowercase().chars().collect();
+    let text_chars: Vec<char> = text.chars().collect();
+
+    let m = query.len();
+    let n = lower_text.len();
+
+    if m == 0 {
+        return Some(FuzzyMatch {
+            score: 0.0,
+            positions: Vec::new(),
+        });
+    }
+    if n == 0 || m > n {
+        return None;
+    }
+
+    // m_score[i][j] / h_score[i][j] mirror the M/H recurrence from the spec:
+    // M is the score of matching query[i] at text[j]; H is the best score of
+    // aligning query[0..=i] ending at-or-before text[j] (allowing a gap after
+    // the match). `gap_score` (the affine-gap "E" state) tracks only paths
+    // that are *currently inside* a gap, kept separate from H so extending
+    // an open gap is distinguishable from opening a new one: H always
+    // includes the zero-gap M value, so reusing it for the extension step
+    // would let every extension undercut the one-time open cost. `back`
+    // records how each M cell was reached for backtracking.
+    let mut m_score = vec![vec![NEG_INFINITY; n]; m];
+    let mut h_score = vec![vec![NEG_INFINITY; n]; m];
+    let mut gap_score = vec![vec![NEG_INFINITY; n]; m];
+    let mut back: Vec<Vec<Dir>> = vec![vec![Dir::Start; n]; m];
+    // Column of the match that opened the gap `gap_score[i][j]` is in.
+    let mut gap_origin = vec![vec![0usize; n]; m];
+
+    for i in 0..m {
+        let qc = query[i];
+        for j in 0..n {
+            if lower_text[j] == qc {
+                let boundary_bonus = if is_boundary(&text_chars, j) {
+                    BONUS_BOUNDARY
+                } else {
+                    0.0
+                };
+
+                let best_dir = if i == 0 {
+                    Some((SCORE_MATCH + boundary_bonus, Dir::Start))
+                } else if j == 0 {
+                    None
+                } else {
+                    let diag = m_score[i - 1][j - 1];
+                    let gap = h_score[i - 1][j - 1];
+                    if diag >= gap && diag > NEG_INFINITY {
+                        Some((diag + SCORE_MATCH + boundary_bonus + BONUS_CONSECUTIVE, Dir::Diag))
+                    } else if gap > NEG_INFINITY {
+                        Some((gap + SCORE_MATCH + boundary_bonus, Dir::Gap))
+                    } else {
+                        None
+                    }
+                };
+
+                if let Some((best, dir)) = best_dir {
+                    m_score[i][j] = best;
+                    back[i][j] = dir;
+                    h_score[i][j] = best;
+                }
+            }
+
+            // Carry the gap state forward through every column, not just
+            // the ones where `text[j]` matches the current query
+            // character, so a gap in `text` that follows a non-matching
+            // character is still reachable from a later row. Opening a new
+            // gap (from a match) pays `SCORE_GAP_START`; continuing one
+            // already open (from `gap_score`, never from `m_score`/`h_score`
+            // directly) pays only the smaller `SCORE_GAP_EXTENSION`.
+            if j > 0 {
+                let open = m_score[i][j - 1] - SCORE_GAP_START;
+                let extend = gap_score[i][j - 1] - SCORE_GAP_EXTENSION;
+                gap_score[i][j] = open.max(extend);
+                gap_origin[i][j] = if open >= extend { j - 1 } else { gap_origin[i][j - 1] };
+                h_score[i][j] = h_score[i][j].max(gap_score[i][j]);
+            }
+        }
+    }
+
+    let (best_j, best_score) = m_score[m - 1]
+        .iter()
+        .enumerate()
+        .filter(|(_, &s)| s > NEG_INFINITY)
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+        .map(|(j, &s)| (j, s))?;
+
+    // Backtrack from (m - 1, best_j) to collect matched positions. A `Gap`
+    // direction means there's a gap in `text` right before this match, so we
+    // jump to the column of the match that opened that gap.
+    let mut positions = Vec::with_capacity(m);
+    let mut i = m - 1;
+    let mut j = best_j;
+    loop {
+        positions.push(j);
+        match back[i][j] {
+            Dir::Start => break,
+            Dir::Diag => {
+                i -= 1;
+                j -= 1;
+            }
+            Dir::Gap => {
+                i -= 1;
+                j = gap_origin[i][j - 1];
+            }
+        }
+    }
+    positions.reverse();
+
+    let normalized = best_score / (1.0 + n as f64 / 120.0);
+
+    Some(FuzzyMatch {
+        score: normalized,
+        positions,
+    })
+}
+
+/// Character-presence bitmask of `text`: bits 0-25 are `a`-`z`, bit 26 is
+/// "any digit", and bit 27 is "anything else". Used as a cheap superset
+/// check to rule out entries that can't possibly contain a query as a
+/// fuzzy subsequence, without running the full matcher on them.
+pub fn char_bag(text: &str) -> u64 {
+    let mut bag = 0u64;
+    for ch in text.to_lowercase().chars() {
+        let bit = match ch {
+            'a'..='z' => ch as u32 - 'a' as u32,
+            '0'..='9' => 26,
+            _ => 27,
+        };
+        bag |= 1 << bit;
+    }
+    bag
+}
+
+/// Maximum edit distance tolerated for a query word of the given length:
+/// none for very short words (where a single typo usually changes the
+/// meaning entirely), 2 otherwise — enough to cover both a transposed
+/// letter and a missing one (e.g. `paet` -> `paste`), not just a single
+/// substitution.
+pub fn typo_budget(len: usize) -> usize {
+    match len {
+        0..=2 => 0,
+        _ => 2,
+    }
+}
+
+/// Bounded Levenshtein distance between `a` and `b`. Only fills DP cells
+/// within `max_distance` of the diagonal (Ukkonen's banded algorithm) and
+/// bails out as soon as a whole row exceeds the budget, so a non-match is
+/// cheap to rule out. Returns `None` when the distance is over budget.
+pub fn bounded_edit_distance(a: &str, b: &str, max_distance: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let n = a.len();
+    let m = b.len();
+
+    if n.abs_diff(m) > max_distance {
+        return None;
+    }
+
+    // `inf` stands in for "already over budget" without risking overflow.
+    let inf = max_distance + 1;
+    let mut prev: Vec<usize> = (0..=m).map(|j| j.min(inf)).collect();
+
+    for i in 1..=n {
+        let lo = i.saturating_sub(max_distance);
+        let hi = (i + max_distance).min(m);
+        let mut cur = vec![inf; m + 1];
+        if lo == 0 {
+            cur[0] = i.min(inf);
+        }
+        for j in lo.max(1)..=hi {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let deletion = prev[j] + 1;
+            let insertion = cur[j - 1] + 1;
+            let substitution = prev[j - 1] + cost;
+            cur[j] = deletion.min(insertion).min(substitution).min(inf);
+        }
+        if cur[lo..=hi].iter().all(|&d| d > max_distance) {
+            return None;
+        }
+        prev = cur;
+    }
+
+    let distance = prev[m];
+    (distance <= max_distance).then_some(distance)
+}
+
+/// Best edit distance of `word` against any whitespace-separated word in
+/// `text`, within `word`'s own typo budget. `None` if `word` is too short
+/// to tolerate any typo, or no word in `text` is within budget.
+pub fn best_word_edit_distance(word: &str, text: &str) -> Option<usize> {
+    let budget = typo_budget(word.chars().count());
+    if budget == 0 {
+        return None;
+    }
+    text.split_whitespace()
+        .filter_map(|candidate| bounded_edit_distance(word, candidate, budget))
+        .min()
+}
+
+/// Split `text` into `(run, is_match)` pieces for an already-computed set of
+/// matched character indices (e.g. from [`FuzzyMatch::positions`]), so a
+/// caller that matched against something other than the raw `text` itself
+/// (a single term out of a multi-term query, say) can still highlight the
+/// right runs. Falls back to a single unmatched run when `positions` is
+/// empty.
+pub fn highlight_positions(text: &str, positions: &[usize]) -> Vec<(String, bool)> {
+    if positions.is_empty() {
+        return vec![(text.to_string(), false)];
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut runs = Vec::new();
+    let mut run = String::new();
+    let mut run_is_match = false;
+
+    for (i, ch) in chars.into_iter().enumerate() {
+        let is_match = positions.binary_search(&i).is_ok();
+        if !run.is_empty() && is_match != run_is_match {
+            runs.push((std::mem::take(&mut run), run_is_match));
+        }
+        run_is_match = is_match;
+        run.push(ch);
+    }
+    if !run.is_empty() {
+        runs.push((run, run_is_match));
+    }
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_match_finds_subsequence_in_order() {
+        let m = fuzzy_match("gac", "git add --all --commit").unwrap();
+        assert_eq!(m.positions, vec![0, 10, 16]);
+    }
+
+    #[test]
+    fn fuzzy_match_rejects_out_of_order_subsequence() {
+        assert_eq!(fuzzy_match("tg", "git"), None);
+    }
+
+    #[test]
+    fn fuzzy_match_empty_query_matches_with_zero_score() {
+        let m = fuzzy_match("", "anything").unwrap();
+        assert_eq!(m.score, 0.0);
+        assert!(m.positions.is_empty());
+    }
+
+    #[test]
+    fn fuzzy_match_scores_boundary_matches_higher_than_mid_word() {
+        let boundary = fuzzy_match("gc", "git commit").unwrap();
+        let mid_word = fuzzy_match("tc", "git commit").unwrap();
+        assert!(boundary.score > mid_word.score);
+    }
+
+    #[test]
+    fn best_word_edit_distance_finds_typo_within_budget() {
+        assert_eq!(best_word_edit_distance("serach", "search box"), Some(2));
+        assert_eq!(best_word_edit_distance("paet", "paste clipboard"), Some(2));
+    }
+
+    #[test]
+    fn bounded_edit_distance_within_budget() {
+        assert_eq!(bounded_edit_distance("kitten", "sitting", 3), Some(3));
+        assert_eq!(bounded_edit_distance("same", "same", 0), Some(0));
+    }
+
+    #[test]
+    fn bounded_edit_distance_over_budget_returns_none() {
+        assert_eq!(bounded_edit_distance("kitten", "sitting", 2), None);
+    }
+}
@@ -0,0 +1,134 @@
+//! Syntax-highlighted rendering for key chips and command hints.
+//!
+//! Highlighting is computed on the backend (a syntect-style highlighter
+//! keyed by `CheatItem.lang`) and shipped here as a flat list of styled
+//! spans, which this component turns into colored `<span>`s.
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::fuzzy::highlight_positions;
+use super::invoke;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StyledSpan {
+    pub text: String,
+    pub color: String,
+    #[serde(default)]
+    pub bold: bool,
+    #[serde(default)]
+    pub italic: bool,
+}
+
+/// Split `spans` into `(span, is_match)` pieces against `positions`
+/// (character indices into the spans' concatenated text), splitting any
+/// span that only partially overlaps a match so a fuzzy-match highlight can
+/// be layered on top of syntax-color spans without losing either.
+fn split_spans_by_positions(spans: &[StyledSpan], positions: &[usize]) -> Vec<(StyledSpan, bool)> {
+    if positions.is_empty() {
+        return spans.iter().cloned().map(|span| (span, false)).collect();
+    }
+
+    let mut out = Vec::new();
+    let mut offset = 0;
+    for span in spans {
+        let chars: Vec<char> = span.text.chars().collect();
+        let mut run = String::new();
+        let mut run_is_match = false;
+        for (i, ch) in chars.iter().enumerate() {
+            let is_match = positions.binary_search(&(offset + i)).is_ok();
+            if !run.is_empty() && is_match != run_is_match {
+                out.push((
+                    StyledSpan {
+                        text: std::mem::take(&mut run),
+                        ..span.clone()
+                    },
+                    run_is_match,
+                ));
+            }
+            run_is_match = is_match;
+            run.push(*ch);
+        }
+        if !run.is_empty() {
+            out.push((StyledSpan { text: run, ..span.clone() }, run_is_match));
+        }
+        offset += chars.len();
+    }
+    out
+}
+
+/// Renders `text` as plain text when `lang` is `None`, otherwise fetches
+/// highlighted spans for it from the backend and renders those instead.
+/// `positions` (character indices matched by a search query, if any) are
+/// wrapped in `<mark>` on top of the syntax coloring.
+#[component]
+pub fn HighlightedText(
+    text: String,
+    lang: Option<String>,
+    #[prop(optional)] positions: Vec<usize>,
+) -> impl IntoView {
+    let (spans, set_spans) = signal(Vec::<StyledSpan>::new());
+    let plain_text = text.clone();
+
+    if let Some(lang) = lang {
+        Effect::new(move |_| {
+            let text = text.clone();
+            let lang = lang.clone();
+            leptos::task::spawn_local(async move {
+                let args = serde_json::json!({ "text": text, "lang": lang });
+                if let Ok(js_args) = serde_wasm_bindgen::to_value(&args) {
+                    let result = invoke("highlight_text", js_args).await;
+                    if !result.is_undefined() && !result.is_null() {
+                        if let Ok(parsed) = serde_wasm_bindgen::from_value::<Vec<StyledSpan>>(result) {
+                            set_spans.set(parsed);
+                        }
+                    }
+                }
+            });
+        });
+    }
+
+    view! {
+        <>
+            {move || {
+                let current = spans.get();
+                if current.is_empty() {
+                    if positions.is_empty() {
+                        view! { <>{plain_text.clone()}</> }.into_any()
+                    } else {
+                        highlight_positions(&plain_text, &positions)
+                            .into_iter()
+                            .map(|(run, is_match)| {
+                                if is_match {
+                                    view! { <mark>{run}</mark> }.into_any()
+                                } else {
+                                    run.into_any()
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .into_any()
+                    }
+                } else {
+                    split_spans_by_positions(&current, &positions)
+                        .into_iter()
+                        .map(|(span, is_match)| {
+                            let style = format!(
+                                "color: {}; font-weight: {}; font-style: {};",
+                                span.color,
+                                if span.bold { "bold" } else { "normal" },
+                                if span.italic { "italic" } else { "normal" },
+                            );
+                            let text = span.text.clone();
+                            if is_match {
+                                view! { <mark><span style=style>{text}</span></mark> }.into_any()
+                            } else {
+                                view! { <span style=style>{text}</span> }.into_any()
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .into_any()
+                }
+            }}
+        </>
+    }
+}
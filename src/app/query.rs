@@ -0,0 +1,292 @@
+//! Multi-term query grammar for the search box.
+//!
+//! A query is split on whitespace into terms that are ANDed together, each
+//! with an optional skim/fzf-style operator prefix:
+//!
+//! - `!foo`  negation: the entry is excluded if the rest of the term matches
+//! - `#foo`  scopes the rest of the term to the entry's tags instead of its
+//!           full text (combinable with the other sigils, e.g. `#^foo`)
+//! - `'foo`  exact (non-fuzzy) substring match
+//! - `^foo`  anchors the term to the start of the scope's text
+//! - `foo$`  anchors the term to the end of the scope's text (`\$` escapes a
+//!           literal trailing `$` instead of anchoring)
+//! - `^foo$` / `'foo$`  anchors the term to both ends at once (the scope's
+//!           text must equal it exactly)
+//! - `foo`   plain fuzzy match (the default)
+//!
+//! A plain fuzzy term also matches through a sheet's declared synonym
+//! groups (see [`score_query`]), so `delete` can surface an entry whose
+//! text only says `remove`. If it isn't a fuzzy subsequence of anything
+//! either, it falls back to a typo-tolerant word match so a quick typo
+//! like `serach` still finds `search`.
+
+use super::fuzzy::{best_word_edit_distance, fuzzy_match, FuzzyMatch};
+
+/// Score contributed by a typo-tolerant match with zero edits, decreasing
+/// per edit so a closer word outranks a more distant one. This sits well
+/// below what a compact fuzzy match scores, but a fuzzy match spread over
+/// long gaps can still score lower.
+const TYPO_BASE_SCORE: f64 = 6.0;
+const TYPO_EDIT_PENALTY: f64 = 2.0;
+
+/// Score `term_text` against `haystack` by finding its closest word within
+/// budget, or `None` if no word in `haystack` is close enough.
+fn typo_score(term_text: &str, haystack: &str) -> Option<f64> {
+    best_word_edit_distance(term_text, haystack)
+        .map(|distance| (TYPO_BASE_SCORE - distance as f64 * TYPO_EDIT_PENALTY).max(0.5))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatchKind {
+    Fuzzy,
+    Exact,
+    Prefix,
+    Suffix,
+    /// Anchored at both ends: `^foo$` / `'foo$`.
+    Anchored,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    Text,
+    Tags,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryTerm {
+    pub scope: Scope,
+    pub kind: MatchKind,
+    pub negate: bool,
+    /// Already-lowercased term text with its operator sigils stripped.
+    pub text: String,
+}
+
+fn parse_term(raw: &str) -> QueryTerm {
+    let negate = raw.starts_with('!');
+    let rest = if negate { &raw[1..] } else { raw };
+
+    let (scope, rest) = if let Some(rest) = rest.strip_prefix('#') {
+        (Scope::Tags, rest)
+    } else {
+        (Scope::Text, rest)
+    };
+
+    let (prefix_kind, rest) = if let Some(rest) = rest.strip_prefix('\'') {
+        (Some(MatchKind::Exact), rest)
+    } else if let Some(rest) = rest.strip_prefix('^') {
+        (Some(MatchKind::Prefix), rest)
+    } else {
+        (None, rest)
+    };
+
+    let (kind, text) = if let Some(rest) = rest.strip_suffix("\\$") {
+        // Escaped `$`: keep it as a literal character instead of treating
+        // it as the end-anchor operator.
+        (prefix_kind.unwrap_or(MatchKind::Fuzzy), format!("{}$", rest.to_lowercase()))
+    } else if let Some(rest) = rest.strip_suffix('$') {
+        let kind = match prefix_kind {
+            Some(MatchKind::Exact) | Some(MatchKind::Prefix) => MatchKind::Anchored,
+            _ => MatchKind::Suffix,
+        };
+        (kind, rest.to_lowercase())
+    } else {
+        (prefix_kind.unwrap_or(MatchKind::Fuzzy), rest.to_lowercase())
+    };
+
+    QueryTerm {
+        scope,
+        kind,
+        negate,
+        text,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub terms: Vec<QueryTerm>,
+}
+
+/// Split `raw` on whitespace into a list of ANDed, operator-tagged terms.
+pub fn parse_query(raw: &str) -> Query {
+    let terms = raw
+        .split_whitespace()
+        .filter(|s| !s.is_empty())
+        .map(parse_term)
+        .collect();
+
+    Query { terms }
+}
+
+/// A term's synonym group (lowercased) if `synonyms` declares one
+/// containing `word`, or just `word` by itself otherwise — so search
+/// behaves exactly as before wherever no synonyms are configured.
+fn synonym_variants(synonyms: &[Vec<String>], word: &str) -> Vec<String> {
+    for group in synonyms {
+        if group.iter().any(|w| w.eq_ignore_ascii_case(word)) {
+            return group.iter().map(|w| w.to_lowercase()).collect();
+        }
+    }
+    vec![word.to_string()]
+}
+
+/// The text variants a fuzzy term should be tried against: its synonym
+/// group if one matches, or just the term's own text. Non-fuzzy terms
+/// (`'exact`, `^prefix`, `suffix$`, `^anchored$`) never expand, since those
+/// operators ask for a specific literal rather than "this concept".
+pub fn term_variants(term: &QueryTerm, synonyms: &[Vec<String>]) -> Vec<String> {
+    if term.kind == MatchKind::Fuzzy {
+        synonym_variants(synonyms, &term.text)
+    } else {
+        vec![term.text.clone()]
+    }
+}
+
+/// The best-scoring fuzzy subsequence match among `query`'s non-negated,
+/// text-scoped fuzzy terms against `text` (trying each term's synonym
+/// variants), or `None` if no such term matches. Used to locate match
+/// positions for ranking (`RankCriterion::Begin`) and `<mark>` highlighting
+/// against the terms that actually matched, instead of re-running the whole
+/// raw query string (which still carries its `!`/`^`/`'`/`$`/`#` sigils and
+/// the spaces between ANDed terms) through the matcher.
+pub fn best_fuzzy_match(query: &Query, text: &str, synonyms: &[Vec<String>]) -> Option<FuzzyMatch> {
+    query
+        .terms
+        .iter()
+        .filter(|term| !term.negate && term.scope == Scope::Text && term.kind == MatchKind::Fuzzy)
+        .flat_map(|term| term_variants(term, synonyms))
+        .filter_map(|variant| fuzzy_match(&variant, text))
+        .fold(None, |best: Option<FuzzyMatch>, candidate| {
+            Some(match best {
+                Some(best) if best.score >= candidate.score => best,
+                _ => candidate,
+            })
+        })
+}
+
+/// Score `query` against an entry's `text` and `tags_text`, requiring every
+/// positive term to match (in its own scope) and no negated term to match.
+/// A fuzzy term also matches through any of its `synonyms`, keeping
+/// whichever variant scores best, and falls back to a typo-tolerant word
+/// match (scored below compact fuzzy matches, though not below ones spread
+/// over long gaps) when no variant is a fuzzy subsequence at all. Returns `None` when the entry doesn't
+/// satisfy the query; otherwise the summed score of the positive terms.
+pub fn score_query(
+    query: &Query,
+    text: &str,
+    tags_text: &str,
+    synonyms: &[Vec<String>],
+) -> Option<f64> {
+    let lower_text = text.to_lowercase();
+    let mut total = 0.0;
+
+    for term in &query.terms {
+        let haystack = match term.scope {
+            Scope::Text => &lower_text,
+            Scope::Tags => tags_text,
+        };
+
+        let fuzzy_best = if term.kind == MatchKind::Fuzzy {
+            let variants = term_variants(term, synonyms);
+            let subsequence_best = variants
+                .iter()
+                .filter_map(|variant| fuzzy_match(variant, haystack))
+                .map(|m| m.score)
+                .fold(None, |best: Option<f64>, score| {
+                    Some(best.map_or(score, |b| b.max(score)))
+                });
+
+            subsequence_best.or_else(|| {
+                variants
+                    .iter()
+                    .filter_map(|variant| typo_score(variant, haystack))
+                    .fold(None, |best: Option<f64>, score| {
+                        Some(best.map_or(score, |b| b.max(score)))
+                    })
+            })
+        } else {
+            None
+        };
+
+        let matched = match term.kind {
+            MatchKind::Exact => haystack.contains(&term.text),
+            MatchKind::Prefix => haystack.starts_with(&term.text),
+            MatchKind::Suffix => haystack.ends_with(&term.text),
+            MatchKind::Anchored => haystack == term.text,
+            MatchKind::Fuzzy => fuzzy_best.is_some(),
+        };
+
+        if term.negate {
+            if term.text.is_empty() || matched {
+                return None;
+            }
+            continue;
+        }
+
+        if !matched {
+            return None;
+        }
+
+        total += match term.kind {
+            MatchKind::Fuzzy => fuzzy_best.unwrap_or(0.0),
+            _ => 1.0,
+        };
+    }
+
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_query_splits_on_whitespace() {
+        let q = parse_query("foo bar");
+        assert_eq!(q.terms.len(), 2);
+        assert_eq!(q.terms[0].text, "foo");
+        assert_eq!(q.terms[1].text, "bar");
+    }
+
+    #[test]
+    fn parse_query_recognizes_operators() {
+        assert!(parse_query("!foo").terms[0].negate);
+        assert_eq!(parse_query("#foo").terms[0].scope, Scope::Tags);
+        assert_eq!(parse_query("'foo").terms[0].kind, MatchKind::Exact);
+        assert_eq!(parse_query("^foo").terms[0].kind, MatchKind::Prefix);
+        assert_eq!(parse_query("foo$").terms[0].kind, MatchKind::Suffix);
+        assert_eq!(parse_query("^foo$").terms[0].kind, MatchKind::Anchored);
+    }
+
+    #[test]
+    fn parse_query_escaped_dollar_is_literal() {
+        let term = &parse_query("foo\\$").terms[0];
+        assert_eq!(term.kind, MatchKind::Fuzzy);
+        assert_eq!(term.text, "foo$");
+    }
+
+    #[test]
+    fn score_query_pure_negation_matches_are_a_real_hit() {
+        let query = parse_query("!git");
+        let score = score_query(&query, "find a file", "", &[]);
+        assert_eq!(score, Some(0.0));
+    }
+
+    #[test]
+    fn score_query_negated_term_present_excludes_entry() {
+        let query = parse_query("!git");
+        assert_eq!(score_query(&query, "git commit", "", &[]), None);
+    }
+
+    #[test]
+    fn score_query_positive_fuzzy_term_scores_above_zero() {
+        let query = parse_query("gc");
+        let score = score_query(&query, "git commit", "", &[]).unwrap();
+        assert!(score > 0.0);
+    }
+
+    #[test]
+    fn score_query_missing_positive_term_excludes_entry() {
+        let query = parse_query("xyz");
+        assert_eq!(score_query(&query, "git commit", "", &[]), None);
+    }
+}